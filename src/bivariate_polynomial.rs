@@ -0,0 +1,174 @@
+use crate::ecfft::EcFft;
+
+use pairing::bn256::Fq as Fp;
+use rayon::join;
+
+// a bivariate polynomial P(X, Y) = sum_{i,j} c_{i,j} X^i Y^j, stored row
+// major: `rows` rows indexed by the X exponent, `cols` columns indexed by Y
+#[derive(Clone, Debug)]
+pub(crate) struct BivariatePolynomial {
+    values: Vec<Fp>,
+    rows: usize,
+    cols: usize,
+}
+
+impl BivariatePolynomial {
+    pub(crate) fn new(values: Vec<Fp>, rows: usize, cols: usize) -> Self {
+        assert_eq!(values.len(), rows * cols);
+        assert!(rows.is_power_of_two());
+        assert!(cols.is_power_of_two());
+
+        BivariatePolynomial { values, rows, cols }
+    }
+
+    pub(crate) fn get_values(self) -> Vec<Fp> {
+        self.values
+    }
+
+    // evaluate over the product of the row and column ECFFT cosets: run the
+    // (row-sized) ecfft along every row, transpose, then run the (row-count
+    // sized) ecfft along every column
+    pub(crate) fn bi_fft(mut self, row_ecfft: &EcFft, col_ecfft: &EcFft) -> Self {
+        let (rows, cols) = (self.rows, self.cols);
+
+        ecfft_rows(&mut self.values, cols, row_ecfft);
+        transpose(&mut self.values, rows, cols);
+        ecfft_rows(&mut self.values, rows, col_ecfft);
+        transpose(&mut self.values, cols, rows);
+
+        self
+    }
+
+    // recover the coefficient matrix from evaluations on the product coset
+    pub(crate) fn bi_ifft(mut self, row_ecfft: &EcFft, col_ecfft: &EcFft) -> Self {
+        let (rows, cols) = (self.rows, self.cols);
+
+        ecfft_rows_inv(&mut self.values, cols, row_ecfft);
+        transpose(&mut self.values, rows, cols);
+        ecfft_rows_inv(&mut self.values, rows, col_ecfft);
+        transpose(&mut self.values, cols, rows);
+
+        self
+    }
+}
+
+// ecfft every `row_len`-sized row of `values`, splitting the row-chunk array
+// in half and recursing with `rayon::join`, as `low_degree_extention` does
+fn ecfft_rows(values: &mut [Fp], row_len: usize, ecfft: &EcFft) {
+    if values.len() == row_len {
+        let evaluated = ecfft.fft(values).get_values();
+        values.copy_from_slice(&evaluated);
+        return;
+    }
+
+    let mid = values.len() / 2;
+    let (left, right) = values.split_at_mut(mid);
+    join(
+        || ecfft_rows(left, row_len, ecfft),
+        || ecfft_rows(right, row_len, ecfft),
+    );
+}
+
+fn ecfft_rows_inv(values: &mut [Fp], row_len: usize, ecfft: &EcFft) {
+    if values.len() == row_len {
+        let coeffs = ecfft.ifft(values).get_values();
+        values.copy_from_slice(&coeffs);
+        return;
+    }
+
+    let mid = values.len() / 2;
+    let (left, right) = values.split_at_mut(mid);
+    join(
+        || ecfft_rows_inv(left, row_len, ecfft),
+        || ecfft_rows_inv(right, row_len, ecfft),
+    );
+}
+
+// transpose an `rows x cols` row-major matrix in place via a scratch buffer
+fn transpose(values: &mut [Fp], rows: usize, cols: usize) {
+    let mut scratch = vec![Fp::zero(); values.len()];
+    scratch.copy_from_slice(values);
+
+    for r in 0..rows {
+        for c in 0..cols {
+            values[c * rows + r] = scratch[r * cols + c];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BivariatePolynomial;
+    use crate::ecfft::EcFft;
+    use crate::test::layer_coset;
+
+    use pairing::bn256::Fq as Fp;
+    use pairing::group::ff::Field;
+    use proptest::prelude::*;
+    use rand_core::OsRng;
+
+    // P(x, y) = sum_{i,j} c_{i,j} x^i y^j, evaluated directly at every point
+    // of `col_coset x row_coset`, for comparison against `bi_fft`
+    fn naive_bi_eval(
+        values: &[Fp],
+        cols: usize,
+        row_coset: &[Fp],
+        col_coset: &[Fp],
+    ) -> Vec<Fp> {
+        col_coset
+            .iter()
+            .flat_map(|x| {
+                row_coset.iter().map(move |y| {
+                    values
+                        .chunks(cols)
+                        .rev()
+                        .fold(Fp::zero(), |acc, row| {
+                            let row_eval = row
+                                .iter()
+                                .rev()
+                                .fold(Fp::zero(), |acc, coeff| acc * y + coeff);
+                            acc * x + row_eval
+                        })
+                })
+            })
+            .collect()
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(100))]
+        #[test]
+        fn test_bi_fft_matches_naive_evaluation(k_rows in 1u32..4, k_cols in 1u32..4) {
+            let rows = 1usize << k_rows;
+            let cols = 1usize << k_cols;
+            let values = (0..rows * cols).map(|_| Fp::random(OsRng)).collect::<Vec<_>>();
+
+            let row_coset = layer_coset(14 - k_cols);
+            let col_coset = layer_coset(14 - k_rows);
+            let row_ecfft = EcFft::new(k_cols, row_coset.clone());
+            let col_ecfft = EcFft::new(k_rows, col_coset.clone());
+
+            let poly = BivariatePolynomial::new(values.clone(), rows, cols);
+            let got = poly.bi_fft(&row_ecfft, &col_ecfft).get_values();
+
+            let expected = naive_bi_eval(&values, cols, &row_coset, &col_coset);
+
+            assert_eq!(got, expected);
+        }
+
+        #[test]
+        fn test_bi_ifft_is_inverse_of_bi_fft(k_rows in 1u32..4, k_cols in 1u32..4) {
+            let rows = 1usize << k_rows;
+            let cols = 1usize << k_cols;
+            let values = (0..rows * cols).map(|_| Fp::random(OsRng)).collect::<Vec<_>>();
+
+            let row_ecfft = EcFft::new(k_cols, layer_coset(14 - k_cols));
+            let col_ecfft = EcFft::new(k_rows, layer_coset(14 - k_rows));
+
+            let poly = BivariatePolynomial::new(values.clone(), rows, cols);
+            let evaluated = poly.bi_fft(&row_ecfft, &col_ecfft);
+            let recovered = evaluated.bi_ifft(&row_ecfft, &col_ecfft).get_values();
+
+            assert_eq!(recovered, values);
+        }
+    }
+}
@@ -3,9 +3,13 @@ mod curve;
 mod isogeny;
 mod utils;
 
-use utils::{swap_bit_reverse, EcFftCache};
+use utils::{matrix_arithmetic, swap_bit_reverse, EcFftCache};
+
+use crate::polynomial::{Coefficients, PointValue, Polynomial};
 
 use pairing::bn256::Fq as Fp;
+use pairing::group::ff::Field;
+use rayon::join;
 
 // precomputed params for ecfft
 #[derive(Clone, Debug)]
@@ -17,27 +21,215 @@ pub(crate) struct EcFft {
 }
 
 impl EcFft {
-    pub fn new(k: u32) -> Self {
-        assert!(k == 14);
-        let cache = EcFftCache::new(k);
+    pub fn new(k: u32, coset: Vec<Fp>) -> Self {
+        assert!(k <= 14);
+        let cache = EcFftCache::new(k as usize, coset);
 
         EcFft { k, cache }
     }
 
-    // perform ecfft
-    pub fn fft(&self, coeffs: &mut [Fp]) {
+    // build params for a degree 2^k polynomial using the crate's canonical coset
+    pub(crate) fn for_degree(k: u32) -> Self {
+        Self::new(k, curve::coset(k))
+    }
+
+    pub(crate) fn coset(&self) -> &Vec<Fp> {
+        self.cache.get_coset()
+    }
+
+    // the roots of the vanishing polynomial of the isogeny layer `depth`'s
+    // subdomain, i.e. the domain points `EcFftCache::extend` moves between
+    pub(crate) fn vanishing_roots(&self, depth: usize) -> Vec<Fp> {
+        let (s, s_prime) = self.cache.get_tree(depth).get_domain();
+        s.iter().chain(s_prime.iter()).cloned().collect()
+    }
+
+    // perform ecfft: transform a degree < 2^k polynomial's coefficients into
+    // its evaluations over the precomputed coset
+    pub fn fft(&self, coeffs: &[Fp]) -> Polynomial<Fp, PointValue> {
         let n = 1 << self.k;
         assert_eq!(coeffs.len(), n);
 
-        swap_bit_reverse(coeffs, n, self.k);
+        let mut coeffs = coeffs.to_vec();
+        swap_bit_reverse(&mut coeffs, n, self.k);
+
+        let values = if n == 1 {
+            coeffs
+        } else {
+            let half = n / 2;
+            let (p0, p1) = coeffs.split_at_mut(half);
+            join(
+                || ecfft_arithmetic(p0, half, 0, &self.cache),
+                || ecfft_arithmetic(p1, half, 0, &self.cache),
+            );
+
+            // p0, p1 now hold P0, P1 evaluated on the shared projected domain
+            // psi(coset[2i]) == psi(coset[2i + 1]); recombine with
+            // P(x) = P0(psi(x)) + x * P1(psi(x)) using the raw coset points,
+            // mirroring the pair bootstrap `EcFftCache::new` performs before
+            // its isogeny loop
+            let coset = self.cache.get_coset();
+            let mut values = vec![Fp::zero(); n];
+            for i in 0..half {
+                values[2 * i] = p0[i] + coset[2 * i] * p1[i];
+                values[2 * i + 1] = p0[i] + coset[2 * i + 1] * p1[i];
+            }
+            values
+        };
+
+        Polynomial::<Fp, PointValue>::from_values(values)
+    }
+
+    // invert ecfft: recover a degree < 2^k polynomial's coefficients from its
+    // evaluations over the precomputed coset
+    pub fn ifft(&self, values: &[Fp]) -> Polynomial<Fp, Coefficients> {
+        let n = 1 << self.k;
+        assert_eq!(values.len(), n);
+
+        let mut coeffs = if n == 1 {
+            values.to_vec()
+        } else {
+            let half = n / 2;
+            let coset = self.cache.get_coset();
+
+            // undo P(x) = P0(psi(x)) + x * P1(psi(x)) for each coset pair to
+            // recover P0, P1's evaluations on the shared projected domain
+            let mut p0 = vec![Fp::zero(); half];
+            let mut p1 = vec![Fp::zero(); half];
+            for i in 0..half {
+                let (a, b) = (coset[2 * i], coset[2 * i + 1]);
+                let (va, vb) = (values[2 * i], values[2 * i + 1]);
+                let inv = (b - a).invert().unwrap();
+                p0[i] = (b * va - a * vb) * inv;
+                p1[i] = (vb - va) * inv;
+            }
+
+            join(
+                || ecfft_arithmetic_inv(&mut p0, half, 0, &self.cache),
+                || ecfft_arithmetic_inv(&mut p1, half, 0, &self.cache),
+            );
+
+            let mut coeffs = vec![Fp::zero(); n];
+            coeffs[..half].copy_from_slice(&p0);
+            coeffs[half..].copy_from_slice(&p1);
+            coeffs
+        };
+
+        // the bit reverse permutation is its own inverse
+        swap_bit_reverse(&mut coeffs, n, self.k);
+
+        Polynomial::<Fp, Coefficients>::new(coeffs)
+    }
+}
+
+// a Reed-Solomon codeword produced by `lde`, paired with the coset it was
+// evaluated on so callers don't have to rederive it
+#[derive(Clone, Debug)]
+pub(crate) struct ReedSolomonCode {
+    pub(crate) coset: Vec<Fp>,
+    pub(crate) codeword: Polynomial<Fp, PointValue>,
+}
+
+// low degree extension: zero-pad `coeffs` (degree < n) up to a coset of size
+// `blowup * n` and evaluate it there
+pub(crate) fn lde(coeffs: &[Fp], blowup: usize) -> ReedSolomonCode {
+    assert!(blowup.is_power_of_two());
+    assert!(coeffs.len().is_power_of_two());
 
-        ecfft_arithmetic(coeffs, n)
+    let mut k = 0u32;
+    while (1usize << k) < coeffs.len() * blowup {
+        k += 1;
     }
+
+    let mut padded = coeffs.to_vec();
+    padded.resize(1 << k, Fp::zero());
+
+    let ecfft = EcFft::for_degree(k);
+    let codeword = ecfft.fft(&padded);
+    let coset = ecfft.cache.get_coset().clone();
+
+    ReedSolomonCode { coset, codeword }
 }
 
 // ecfft using divide and conquer algorithm
-fn ecfft_arithmetic(coeffs: &mut [Fp], n: usize) {
+fn ecfft_arithmetic(coeffs: &mut [Fp], n: usize, depth: usize, cache: &EcFftCache) {
+    if n == 1 {
+        return;
+    }
+
+    let tree = cache.get_tree(depth);
+    let (p0, p1) = coeffs.split_at_mut(n / 2);
+    join(
+        || ecfft_arithmetic(p0, n / 2, depth + 1, cache),
+        || ecfft_arithmetic(p1, n / 2, depth + 1, cache),
+    );
+    matrix_arithmetic(p0, p1, tree.get_factor())
+}
+
+// inverse ecfft using divide and conquer algorithm
+fn ecfft_arithmetic_inv(values: &mut [Fp], n: usize, depth: usize, cache: &EcFftCache) {
     if n == 1 {
-    } else {
+        return;
+    }
+
+    let tree = cache.get_tree(depth);
+    let (p0, p1) = values.split_at_mut(n / 2);
+    matrix_arithmetic(p0, p1, tree.get_inv_factor());
+    join(
+        || ecfft_arithmetic_inv(p0, n / 2, depth + 1, cache),
+        || ecfft_arithmetic_inv(p1, n / 2, depth + 1, cache),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{lde, EcFft, Fp};
+    use crate::polynomial::{Coefficients, Polynomial};
+    use crate::test::{arb_poly_fq, layer_coset};
+    use pairing::group::ff::Field;
+    use proptest::prelude::*;
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(100))]
+        #[test]
+        fn test_fft_matches_naive_evaluation(k in 2u32..6) {
+            let depth = 14 - k;
+            let coset = layer_coset(depth);
+            let ecfft = EcFft::new(k, coset.clone());
+
+            let poly = arb_poly_fq(k);
+            let expected = poly.to_point_value(&coset);
+
+            let got = ecfft.fft(&poly.clone().get_values());
+
+            assert_eq!(got, expected);
+        }
+
+        #[test]
+        fn test_ifft_is_inverse_of_fft(k in 2u32..6) {
+            let depth = 14 - k;
+            let coset = layer_coset(depth);
+            let ecfft = EcFft::new(k, coset);
+
+            let poly = arb_poly_fq(k);
+            let values = ecfft.fft(&poly.clone().get_values());
+            let recovered = ecfft.ifft(&values.get_values());
+
+            assert_eq!(recovered, poly);
+        }
+
+        #[test]
+        fn test_lde_matches_naive_evaluation(k in 2u32..5, blowup_bits in 0u32..3) {
+            let poly = arb_poly_fq(k);
+            let blowup = 1usize << blowup_bits;
+
+            let code = lde(&poly.clone().get_values(), blowup);
+
+            let mut padded = poly.get_values();
+            padded.resize(code.coset.len(), Fp::zero());
+            let expected = Polynomial::<Fp, Coefficients>::new(padded).to_point_value(&code.coset);
+
+            assert_eq!(code.codeword, expected);
+        }
     }
 }
@@ -3,15 +3,17 @@ use crate::polynomial::{PointValue, Polynomial};
 
 use pairing::bn256::Fq as Fp;
 use rayon::join;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug)]
+// serde-(de)serializable so the isogeny chain walk in `new` can be precomputed once
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub(crate) struct EcFftCache {
     pub(crate) k: usize,
     pub(crate) trees: Vec<FfTree>,
     pub(crate) coset: Vec<Fp>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub(crate) struct FfTree {
     // evaluation domain same size with polynomial
     domain: (Vec<Fp>, Vec<Fp>),
@@ -74,11 +76,23 @@ impl EcFftCache {
         &self.trees[depth]
     }
 
-    #[cfg(test)]
     pub(crate) fn get_coset(&self) -> &Vec<Fp> {
         &self.coset
     }
 
+    // serialize the cache to `path` with bincode, so the isogeny chain walk
+    // in `new` only has to be paid once per coset/degree
+    pub(crate) fn save(&self, path: &str) {
+        let bytes = bincode::serialize(self).unwrap();
+        std::fs::write(path, bytes).unwrap();
+    }
+
+    // deserialize a cache previously written by `save`
+    pub(crate) fn load(path: &str) -> Self {
+        let bytes = std::fs::read(path).unwrap();
+        bincode::deserialize(&bytes).unwrap()
+    }
+
     // evaluate n/2 size of polynomial on n size coset
     pub(crate) fn extend(&self, poly: &mut Polynomial<Fp, PointValue>) {
         let n = 1 << (self.k - 1);
@@ -230,4 +244,29 @@ mod tests {
             assert_eq!(coeff_a_on_s, point_value_a_on_s_prime);
         }
     }
+
+    #[test]
+    fn test_cache_save_load_round_trip() {
+        let k = 2;
+        let coset = layer_coset(14 - k);
+        let cache = EcFftCache::new(k, coset.clone());
+
+        let path = std::env::temp_dir().join("ecfft_cache_round_trip_test.bin");
+        let path = path.to_str().unwrap();
+        cache.save(path);
+        let loaded = EcFftCache::load(path);
+        std::fs::remove_file(path).unwrap();
+
+        let poly = arb_poly_fq(k - 1);
+        let (s, s_prime) = cache.get_tree(0).get_domain();
+        let (loaded_s, loaded_s_prime) = loaded.get_tree(0).get_domain();
+        assert_eq!(s, loaded_s);
+        assert_eq!(s_prime, loaded_s_prime);
+
+        let mut on_s = poly.to_point_value(s);
+        cache.extend(&mut on_s);
+        let mut loaded_on_s = poly.to_point_value(loaded_s);
+        loaded.extend(&mut loaded_on_s);
+        assert_eq!(on_s, loaded_on_s);
+    }
 }
@@ -0,0 +1,220 @@
+use crate::ecfft::EcFft;
+use crate::polynomial::{Coefficients, PointValue, Polynomial};
+
+use pairing::bn256::Fq as Fp;
+use pairing::group::ff::Field;
+
+// a coset-backed evaluation domain for SNARK-style provers, built on top of `EcFft`
+#[derive(Clone, Debug)]
+pub(crate) struct EvaluationDomain {
+    ecfft: EcFft,
+}
+
+impl EvaluationDomain {
+    pub(crate) fn new(k: u32) -> Self {
+        EvaluationDomain {
+            ecfft: EcFft::for_degree(k),
+        }
+    }
+
+    pub(crate) fn fft(&self, coeffs: &[Fp]) -> Polynomial<Fp, PointValue> {
+        self.ecfft.fft(coeffs)
+    }
+
+    pub(crate) fn ifft(&self, values: &[Fp]) -> Polynomial<Fp, Coefficients> {
+        self.ecfft.ifft(values)
+    }
+
+    pub(crate) fn coset(&self) -> &Vec<Fp> {
+        self.ecfft.coset()
+    }
+
+    // scale coefficients by successive powers of `base`, e.g. to shift a
+    // polynomial onto a different coset before evaluating it
+    pub(crate) fn distribute_powers(&self, coeffs: &mut [Fp], base: Fp) {
+        let mut power = Fp::one();
+        coeffs.iter_mut().for_each(|coeff| {
+            *coeff *= power;
+            power *= base;
+        });
+    }
+
+    pub(crate) fn mul_assign(
+        &self,
+        a: &mut Polynomial<Fp, PointValue>,
+        b: &Polynomial<Fp, PointValue>,
+    ) {
+        assert_eq!(a.values.len(), b.values.len());
+        a.values
+            .iter_mut()
+            .zip(b.values.iter())
+            .for_each(|(x, y)| *x *= y);
+    }
+
+    pub(crate) fn sub_assign(
+        &self,
+        a: &mut Polynomial<Fp, PointValue>,
+        b: &Polynomial<Fp, PointValue>,
+    ) {
+        assert_eq!(a.values.len(), b.values.len());
+        a.values
+            .iter_mut()
+            .zip(b.values.iter())
+            .for_each(|(x, y)| *x -= y);
+    }
+
+    // divide evaluations over the coset by the vanishing polynomial of the
+    // `depth`-th isogeny layer's subdomain, the ECFFT analogue of dividing a
+    // quotient polynomial by `Z_H` in a multiplicative-subgroup prover
+    //
+    // `depth` must be > 0: `EcFftCache::new` builds the depth-0 domain by
+    // literally partitioning `self.coset()` into evens/odds, so `depth == 0`'s
+    // subdomain *is* `self.coset()` (reordered) and its vanishing polynomial
+    // is identically zero on every coset point. Every deeper layer's domain
+    // lives under at least one isogeny image and is disjoint from the coset.
+    pub(crate) fn divide_by_vanishing_poly(
+        &self,
+        values: &mut Polynomial<Fp, PointValue>,
+        depth: usize,
+    ) {
+        assert!(
+            depth > 0,
+            "depth 0's subdomain coincides with self.coset(); its vanishing \
+             polynomial is zero there and can't be divided by"
+        );
+        let roots = self.ecfft.vanishing_roots(depth);
+        let coset = self.coset();
+        assert_eq!(values.values.len(), coset.len());
+
+        // build Z_H's coefficients via a subproduct tree (pairing up factors
+        // with ecfft-based `multiply` instead of one big naive fold) and then
+        // reuse the coset's own fft to evaluate Z_H at every coset point in
+        // one O(n log n) pass, rather than a per-point O(n * |roots|) scan
+        let mut z_coeffs = vanishing_poly_coeffs(&roots);
+        z_coeffs.resize(coset.len(), Fp::zero());
+        let z_values = self.fft(&z_coeffs);
+
+        values
+            .values
+            .iter_mut()
+            .zip(z_values.values.iter())
+            .for_each(|(value, z)| *value *= z.invert().unwrap());
+    }
+}
+
+// coefficients of Π(x - root), built by recursively pairing factors and
+// multiplying the halves with the ecfft-based `Polynomial::multiply`, so the
+// whole product costs O(m log^2 m) instead of one O(m^2) naive fold
+fn vanishing_poly_coeffs(roots: &[Fp]) -> Vec<Fp> {
+    if roots.len() == 1 {
+        return vec![-roots[0], Fp::one()];
+    }
+    let mid = roots.len() / 2;
+    let left = Polynomial::<Fp, Coefficients>::new(vanishing_poly_coeffs(&roots[..mid]));
+    let right = Polynomial::<Fp, Coefficients>::new(vanishing_poly_coeffs(&roots[mid..]));
+    left.multiply(right).get_values()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EvaluationDomain;
+    use crate::polynomial::{Coefficients, PointValue, Polynomial};
+    use crate::test::arb_poly_fq;
+
+    use pairing::bn256::Fq as Fp;
+    use pairing::group::ff::Field;
+    use proptest::prelude::*;
+    use rand_core::OsRng;
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(100))]
+        #[test]
+        fn test_distribute_powers_scales_by_successive_powers(k in 2u32..6) {
+            let domain = EvaluationDomain::new(k);
+            let base = Fp::random(OsRng);
+            let original = (0..1usize << k).map(|_| Fp::random(OsRng)).collect::<Vec<_>>();
+
+            let mut got = original.clone();
+            domain.distribute_powers(&mut got, base);
+
+            let mut power = Fp::one();
+            let expected = original.iter().map(|coeff| {
+                let scaled = *coeff * power;
+                power *= base;
+                scaled
+            }).collect::<Vec<_>>();
+
+            assert_eq!(got, expected);
+        }
+
+        #[test]
+        fn test_mul_assign_and_sub_assign_are_pointwise(k in 2u32..6) {
+            let domain = EvaluationDomain::new(k);
+            let n = 1usize << k;
+            let a = (0..n).map(|_| Fp::random(OsRng)).collect::<Vec<_>>();
+            let b = (0..n).map(|_| Fp::random(OsRng)).collect::<Vec<_>>();
+
+            let mut mul = Polynomial::<Fp, PointValue>::from_values(a.clone());
+            let b_values = Polynomial::<Fp, PointValue>::from_values(b.clone());
+            domain.mul_assign(&mut mul, &b_values);
+            let expected_mul = a.iter().zip(b.iter()).map(|(x, y)| *x * y).collect::<Vec<_>>();
+            assert_eq!(mul.values, expected_mul);
+
+            let mut sub = Polynomial::<Fp, PointValue>::from_values(a.clone());
+            domain.sub_assign(&mut sub, &b_values);
+            let expected_sub = a.iter().zip(b.iter()).map(|(x, y)| *x - y).collect::<Vec<_>>();
+            assert_eq!(sub.values, expected_sub);
+        }
+
+        #[test]
+        fn test_divide_by_vanishing_poly_recovers_quotient(k in 4u32..6) {
+            let domain = EvaluationDomain::new(k);
+            let depth = 1usize;
+            let n = 1usize << k;
+
+            let roots = domain.ecfft.vanishing_roots(depth);
+            let m = roots.len();
+
+            // Z_H's coefficients, built directly from its roots by repeated
+            // naive multiplication by (x - root) rather than going through
+            // the implementation's own `vanishing_poly_coeffs` helper
+            let mut z_coeffs = vec![Fp::one()];
+            for root in &roots {
+                let mut next = vec![Fp::zero(); z_coeffs.len() + 1];
+                for (i, coeff) in z_coeffs.iter().enumerate() {
+                    next[i] -= *coeff * root;
+                    next[i + 1] += *coeff;
+                }
+                z_coeffs = next;
+            }
+
+            // an arbitrary quotient t(x), low enough degree that t(x)*Z_H(x)
+            // still fits in the n-sized coset
+            let t = arb_poly_fq(k - 1);
+            let t_coeffs = t.get_values();
+
+            let mut product_coeffs = Polynomial::<Fp, Coefficients>::new(t_coeffs.clone())
+                .multiply(Polynomial::<Fp, Coefficients>::new(z_coeffs))
+                .get_values();
+            product_coeffs.resize(n, Fp::zero());
+
+            let mut values = domain.fft(&product_coeffs);
+            domain.divide_by_vanishing_poly(&mut values, depth);
+
+            let mut t_padded = t_coeffs;
+            t_padded.resize(n, Fp::zero());
+            let expected = domain.fft(&t_padded);
+
+            assert_eq!(m, n / 2);
+            assert_eq!(values, expected);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_divide_by_vanishing_poly_rejects_depth_zero() {
+        let domain = EvaluationDomain::new(3);
+        let mut values = domain.fft(&vec![Fp::zero(); 8]);
+        domain.divide_by_vanishing_poly(&mut values, 0);
+    }
+}
@@ -1,8 +1,11 @@
+use pairing::bn256::Fq as Fp;
 use pairing::group::ff::Field;
 
 use std::fmt::Debug;
 use std::marker::PhantomData;
 
+use crate::ecfft::EcFft;
+
 /// The basis over which a polynomial is described.
 pub trait Basis: Copy + Debug + Send + Sync {}
 
@@ -34,6 +37,13 @@ impl<F: Field, B: Basis> Polynomial<F, B> {
         self.values
     }
 
+    pub(crate) fn from_values(values: Vec<F>) -> Polynomial<F, PointValue> {
+        Polynomial {
+            values,
+            _marker: PhantomData,
+        }
+    }
+
     // order(n) polynomials points multiplication
     pub fn point_multiply(self, b: Polynomial<F, PointValue>) -> Polynomial<F, PointValue> {
         let values = self
@@ -84,6 +94,32 @@ impl<F: Field, B: Basis> Polynomial<F, B> {
     }
 }
 
+impl Polynomial<Fp, Coefficients> {
+    // ecfft-based multiplication over BN256's Fq, replacing `naive_multiply`'s
+    // O(n^2) cost with the usual evaluate/pointwise/interpolate pipeline, run
+    // over the elliptic curve domain since Fq lacks a large 2-adic subgroup
+    pub fn multiply(self, b: Polynomial<Fp, Coefficients>) -> Polynomial<Fp, Coefficients> {
+        let degree = self.values.len() + b.values.len() - 1;
+        let mut k = 0u32;
+        while (1usize << k) < degree {
+            k += 1;
+        }
+        let n = 1usize << k;
+
+        let mut a_coeffs = self.values;
+        a_coeffs.resize(n, Fp::zero());
+        let mut b_coeffs = b.values;
+        b_coeffs.resize(n, Fp::zero());
+
+        let ecfft = EcFft::for_degree(k);
+        let a_values = ecfft.fft(&a_coeffs);
+        let b_values = ecfft.fft(&b_coeffs);
+        let c_values = a_values.point_multiply(b_values);
+
+        ecfft.ifft(&c_values.get_values())
+    }
+}
+
 impl<F: Field, B: Basis> PartialEq for Polynomial<F, B> {
     fn eq(&self, other: &Self) -> bool {
         self.values == other.values
@@ -128,5 +164,22 @@ mod tests {
 
             assert_eq!(poly_a.polynomial_evaluation(point), eval)
         }
+
+        #[test]
+        fn test_multiply_matches_naive_multiply(k_a in 1u32..4, k_b in 1u32..4) {
+            let poly_a = arb_poly(k_a);
+            let poly_b = arb_poly(k_b);
+
+            let naive = poly_a.clone().naive_multiply(poly_b.clone()).get_values();
+            let ecfft = poly_a.multiply(poly_b).get_values();
+
+            let len = naive.len().max(ecfft.len());
+            let pad = |mut values: Vec<Fq>| {
+                values.resize(len, Fq::zero());
+                values
+            };
+
+            assert_eq!(pad(naive), pad(ecfft))
+        }
     }
 }